@@ -23,6 +23,30 @@ use std::{borrow::Cow, collections::HashMap};
 use crate::frame_format::FrameFormat;
 use crate::types::FrameRate;
 
+/// Identifies one of possibly several simultaneous output streams opened from a single physical
+/// camera via [`CaptureTrait::open_streams()`]. Opaque and only meaningful to the backend that
+/// issued it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StreamId(pub u32);
+
+/// Per-frame metadata delivered alongside a [`Buffer`] by
+/// [`CaptureTrait::frame_with_meta()`], so downstream code can detect dropped frames (gaps in
+/// `sequence`) and compute true inter-frame latency — both impossible when successive
+/// [`frame()`](CaptureTrait::frame()) calls are indistinguishable.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FrameMetadata {
+    /// Monotonically incrementing counter for backends that track sequencing with a
+    /// [`FrameSequencer`]. A gap between the `sequence` of two consecutive deliveries means at
+    /// least one frame was dropped in between. The default
+    /// [`frame_with_meta()`](CaptureTrait::frame_with_meta()) keeps no sequencing state and always
+    /// reports `0`.
+    pub sequence: u64,
+    /// Wall-clock time at which the backend dequeued the underlying buffer from the device. The
+    /// default [`frame_with_meta()`](CaptureTrait::frame_with_meta()) takes it after
+    /// [`frame()`](CaptureTrait::frame()) returns instead, so it includes any decode latency.
+    pub timestamp: std::time::Duration,
+}
+
 /// This trait is for any backend that allows you to grab and take frames from a camera.
 /// Many of the backends are **blocking**, if the camera is occupied the library will block while it waits for it to become available.
 ///
@@ -82,6 +106,26 @@ pub trait CaptureTrait {
     /// This will error if the camera is not queryable or a query operation has failed. Some backends will error this out as a Unsupported Operation ([`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError)).
     fn compatible_fourcc(&mut self) -> Result<Vec<FrameFormat>, NokhwaError>;
 
+    /// Resolves a loose `request` (e.g. "~1080p, ~30fps, prefer MJPEG") into the closest
+    /// qualifying [`CameraFormat`] the device actually supports, by scoring every format from
+    /// [`compatible_camera_formats()`](CaptureTrait::compatible_camera_formats()) against
+    /// `request`'s target format with [`Distance::distance_from()`] and returning the
+    /// minimum-cost match, rather than requiring an exact match.
+    /// # Errors
+    /// This will error if the compatible format list cannot be queried, or the device has no
+    /// compatible formats to negotiate against.
+    fn negotiate_format(&mut self, request: &FormatRequest) -> Result<CameraFormat, NokhwaError> {
+        let target = request.format();
+        self.compatible_camera_formats()?
+            .into_iter()
+            .min_by_key(|candidate| candidate.distance_from(&target))
+            .ok_or_else(|| {
+                NokhwaError::ReadFrameError(
+                    "no compatible camera formats available to negotiate".to_string(),
+                )
+            })
+    }
+
     /// Gets the current camera resolution (See: [`Resolution`], [`CameraFormat`]). This will force refresh to the current latest if it has changed.
     fn resolution(&self) -> Option<Resolution>;
 
@@ -159,6 +203,73 @@ pub trait CaptureTrait {
     /// If the backend fails to get the frame (e.g. already taken, busy, doesn't exist anymore), or [`open_stream()`](CaptureTrait::open_stream()) has not been called yet, this will error.
     fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError>;
 
+    /// Gets a frame from the camera like [`frame()`](CaptureTrait::frame()), alongside
+    /// [`FrameMetadata`] describing when it was dequeued and its place in the stream's frame
+    /// sequence. This lets callers detect dropped frames (gaps in
+    /// [`sequence`](FrameMetadata::sequence)) and compute true inter-frame latency.
+    ///
+    /// The default implementation has no sequencing state of its own to draw on, so it always
+    /// reports sequence `0`, and since [`frame()`](CaptureTrait::frame()) is opaque here it can
+    /// only timestamp *after* the call returns (so any decode/conversion latency is included).
+    /// Backends that track real per-stream sequence numbers should override this using a
+    /// [`FrameSequencer`], capturing the timestamp with [`FrameSequencer::now()`] right after the
+    /// raw device dequeue and before any decode step, so the timestamp reflects true dequeue time.
+    /// # Errors
+    /// Same as [`frame()`](CaptureTrait::frame()).
+    fn frame_with_meta(&mut self) -> Result<(Buffer, FrameMetadata), NokhwaError> {
+        let buffer = self.frame()?;
+        let metadata = FrameMetadata {
+            sequence: 0,
+            timestamp: FrameSequencer::now(),
+        };
+        Ok((buffer, metadata))
+    }
+
+    /// Opens one additional output stream per entry in `requests`, alongside whatever stream is
+    /// already open, mirroring the camera3 model where one physical device can feed several
+    /// output streams at once (e.g. a high-res still stream plus a low-res preview stream).
+    /// Backends that can only drive a single hardware format may emulate the extra streams by
+    /// downscaling/reformatting the primary capture.
+    ///
+    /// The default implementation is for backends that cannot multiplex streams at all.
+    /// # Errors
+    /// Returns [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError) unless
+    /// overridden by the backend, or if opening any of the requested streams fails.
+    fn open_streams(&mut self, requests: &[FormatRequest]) -> Result<Vec<StreamId>, NokhwaError> {
+        let _ = requests;
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Gets the next [`Buffer`] for the stream identified by `id`, as previously returned by
+    /// [`open_streams()`](CaptureTrait::open_streams()).
+    /// # Errors
+    /// If `id` does not refer to a currently open stream, or the backend fails to get the frame,
+    /// this will error. Returns [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError)
+    /// unless overridden by the backend.
+    fn frame_for(&mut self, id: StreamId) -> Result<Buffer, NokhwaError> {
+        let _ = id;
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Sets the desired output [`FrameFormat`] for [`frame()`](CaptureTrait::frame()),
+    /// independent of whatever [`FrameFormat`] the device actually negotiated. Pass `None` to
+    /// return frames in the negotiated device format (the default). Backends that support this
+    /// typically implement it with a [`Converter`].
+    /// # Errors
+    /// Returns [`UnsupportedOperationError`](NokhwaError::UnsupportedOperationError) unless
+    /// overridden by the backend.
+    fn set_output_format(&mut self, format: Option<FrameFormat>) -> Result<(), NokhwaError> {
+        let _ = format;
+        Err(NokhwaError::UnsupportedOperationError(self.backend()))
+    }
+
+    /// Gets the output [`FrameFormat`] previously set with
+    /// [`set_output_format()`](CaptureTrait::set_output_format()), or `None` if frames are
+    /// returned in the negotiated device format.
+    fn output_format(&self) -> Option<FrameFormat> {
+        None
+    }
+
     // #[cfg(feature = "wgpu-types")]
     // #[cfg_attr(feature = "docs-features", doc(cfg(feature = "wgpu-types")))]
     // /// Directly copies a frame to a Wgpu texture. This will automatically convert the frame into a RGBA frame.
@@ -387,3 +498,889 @@ pub trait AsyncOpenCaptureTrait: AsyncCaptureTrait {
 pub trait Distance<T> where T: PartialEq {
     fn distance_from(&self, other: &Self) -> T;
 }
+
+/// Weight applied to the framerate term of [`CameraFormat`]'s [`Distance<u64>`] implementation,
+/// so that a 1 FPS difference costs as much as 1000 pixels of resolution difference rather than
+/// being drowned out by it.
+const FORMAT_FRAME_RATE_WEIGHT: u64 = 1000;
+
+/// Fixed penalty [`CameraFormat`]'s [`Distance<u64>`] implementation adds when two formats don't
+/// share the same [`FrameFormat`], large enough that format preference always dominates the
+/// resolution and framerate terms.
+const FORMAT_MISMATCH_PENALTY: u64 = 1_000_000_000;
+
+impl Distance<u64> for CameraFormat {
+    /// Scores how far `self` is from `other`, for use when picking the closest qualifying
+    /// format out of a list of candidates: resolution mismatch contributes
+    /// `|w_self*h_self - w_other*h_other|`, framerate mismatch contributes a scaled
+    /// `|fps_self - fps_other|`, and a non-matching [`FrameFormat`] adds a large fixed penalty so
+    /// format preference dominates. Lower is closer.
+    fn distance_from(&self, other: &Self) -> u64 {
+        let self_pixels = u64::from(self.resolution().width()) * u64::from(self.resolution().height());
+        let other_pixels = u64::from(other.resolution().width()) * u64::from(other.resolution().height());
+        let resolution_cost = self_pixels.abs_diff(other_pixels);
+
+        let frame_rate_cost =
+            u64::from(self.frame_rate()).abs_diff(u64::from(other.frame_rate())) * FORMAT_FRAME_RATE_WEIGHT;
+
+        let format_cost = if self.format() == other.format() {
+            0
+        } else {
+            FORMAT_MISMATCH_PENALTY
+        };
+
+        resolution_cost + frame_rate_cost + format_cost
+    }
+}
+
+/// How many decoded [`Buffer`]s a callback stream will hold before it starts dropping the
+/// oldest one to keep a slow consumer from stalling the capture thread.
+const CALLBACK_STREAM_QUEUE_DEPTH: usize = 4;
+
+/// Push-based counterpart to [`CaptureTrait`]. Instead of the caller polling
+/// [`frame()`](CaptureTrait::frame()) in a loop, implementors spawn a dedicated worker thread
+/// that does the polling and hands each decoded [`Buffer`] to a user-supplied callback. This
+/// decouples slow consumers (GUI redraws, inference loops) from the timing of the underlying
+/// device via an internal bounded queue: if the consumer falls behind, the oldest pending frame
+/// is dropped rather than blocking the capture thread.
+pub trait CallbackCapture: CaptureTrait {
+    /// Spawns a worker thread that loops [`frame()`](CaptureTrait::frame()) and dispatches every
+    /// decoded [`Buffer`] to `callback`. Calling this while a callback stream is already open is
+    /// a no-op.
+    /// # Errors
+    /// If the backend fails to open the stream, this will error.
+    fn start_callback_stream<F>(&mut self, callback: F) -> Result<(), NokhwaError>
+    where
+        F: FnMut(Buffer) + Send + 'static;
+
+    /// Returns `true` if a callback worker thread spawned by
+    /// [`start_callback_stream()`](CallbackCapture::start_callback_stream()) is currently running.
+    fn is_callback_stream_open(&self) -> bool;
+
+    /// Stops the callback worker thread started by
+    /// [`start_callback_stream()`](CallbackCapture::start_callback_stream()), if any, and joins it.
+    /// This does **not** call [`stop_stream()`](CaptureTrait::stop_stream()) on the underlying
+    /// capture; call that separately if you also want to close the device.
+    /// # Errors
+    /// If the worker thread panicked, this will error.
+    fn stop_callback_stream(&mut self) -> Result<(), NokhwaError>;
+}
+
+/// Bounded `Buffer` queue shared between the capture request thread and the dispatch thread.
+/// Unlike `mpsc::sync_channel`'s `try_send`, which refuses the *incoming* frame once full,
+/// [`FrameQueue::push_evicting_oldest()`] drops the oldest queued frame to make room, since for
+/// live video the newest frame is always the more useful one to deliver next.
+#[derive(Default)]
+struct FrameQueue {
+    buffers: std::sync::Mutex<std::collections::VecDeque<Buffer>>,
+    ready: std::sync::Condvar,
+}
+
+impl FrameQueue {
+    /// Pushes `buffer` onto the queue, dropping the oldest queued frame first if already at
+    /// [`CALLBACK_STREAM_QUEUE_DEPTH`].
+    fn push_evicting_oldest(&self, buffer: Buffer) {
+        let mut buffers = match self.buffers.lock() {
+            Ok(buffers) => buffers,
+            Err(_) => return,
+        };
+        if buffers.len() >= CALLBACK_STREAM_QUEUE_DEPTH {
+            buffers.pop_front();
+        }
+        buffers.push_back(buffer);
+        self.ready.notify_one();
+    }
+
+    /// Blocks until a frame is available or `closed` is set, returning `None` in the latter case
+    /// once the queue has been drained.
+    fn pop_blocking(&self, closed: &std::sync::atomic::AtomicBool) -> Option<Buffer> {
+        let mut buffers = self.buffers.lock().ok()?;
+        loop {
+            if let Some(buffer) = buffers.pop_front() {
+                return Some(buffer);
+            }
+            if closed.load(std::sync::atomic::Ordering::Acquire) {
+                return None;
+            }
+            buffers = self.ready.wait(buffers).ok()?;
+        }
+    }
+}
+
+/// Shared state used by [`CallbackCaptureState`] to coordinate shutdown of the capture request
+/// thread with whatever holds the [`CallbackCapture`] implementor.
+struct CallbackStreamShutdown {
+    running: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Cleared by the capture request thread itself right before it exits, whether that's
+    /// because [`stop()`](CallbackCaptureState::stop()) was called or the device dropped out
+    /// from under it. This is what [`CallbackCaptureState::is_open()`] actually reports, rather
+    /// than mere presence of `handle`.
+    alive: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl CallbackStreamShutdown {
+    fn stop(&mut self) -> Result<(), NokhwaError> {
+        self.running.store(false, std::sync::atomic::Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            handle.join().map_err(|_| {
+                NokhwaError::ReadFrameError("callback stream worker thread panicked".to_string())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Drop-in field for a [`CaptureTrait`] implementor that wants [`CallbackCapture`] support:
+/// owns the capture request thread's handle and its shutdown flags. The capture request thread
+/// hands frames to the dispatch thread that actually invokes the user's callback through a
+/// bounded queue shared by the two threads.
+#[derive(Default)]
+pub struct CallbackCaptureState {
+    shutdown: Option<CallbackStreamShutdown>,
+}
+
+impl CallbackCaptureState {
+    /// Starts the capture request thread and the callback dispatch thread for `capture`.
+    ///
+    /// `capture` is shared (not borrowed) with the capture request thread for as long as the
+    /// stream runs, via `Arc<Mutex<_>>`, so the thread always operates on a live, pinned
+    /// allocation no matter what the caller does with its own handle afterwards (move it, box
+    /// it, push it into a `Vec`, ...). Pass in a clone of your own `Arc` if you need to keep
+    /// using `capture` (e.g. to call other [`CaptureTrait`] methods) while the stream is open.
+    /// # Errors
+    /// If the backend fails to open the stream, or the capture's lock is poisoned, this will
+    /// error.
+    pub fn start<T, F>(
+        &mut self,
+        capture: std::sync::Arc<std::sync::Mutex<T>>,
+        mut callback: F,
+    ) -> Result<(), NokhwaError>
+    where
+        T: CaptureTrait + Send + 'static,
+        F: FnMut(Buffer) + Send + 'static,
+    {
+        if self.is_open() {
+            return Ok(());
+        }
+
+        {
+            let mut guard = capture
+                .lock()
+                .map_err(|_| NokhwaError::ReadFrameError("capture lock poisoned".to_string()))?;
+            if !guard.is_stream_open() {
+                guard.open_stream()?;
+            }
+        }
+
+        let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let worker_running = running.clone();
+        let alive = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let worker_alive = alive.clone();
+        let queue = std::sync::Arc::new(FrameQueue::default());
+        let dispatch_queue = queue.clone();
+        let queue_closed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let dispatch_closed = queue_closed.clone();
+
+        let dispatch_handle = std::thread::Builder::new()
+            .name("nokhwa callback dispatch thread".to_string())
+            .spawn(move || {
+                while let Some(buffer) = dispatch_queue.pop_blocking(&dispatch_closed) {
+                    callback(buffer);
+                }
+            })
+            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+
+        let capture_handle = std::thread::Builder::new()
+            .name("nokhwa capture request thread".to_string())
+            .spawn(move || {
+                while worker_running.load(std::sync::atomic::Ordering::Acquire) {
+                    let frame = match capture.lock() {
+                        Ok(mut guard) => guard.frame(),
+                        Err(_) => break,
+                    };
+                    match frame {
+                        Ok(buffer) => queue.push_evicting_oldest(buffer),
+                        Err(_) => break,
+                    }
+                }
+                worker_alive.store(false, std::sync::atomic::Ordering::Release);
+                // Set `closed` while holding the queue lock, so the dispatch thread cannot miss the
+                // wakeup between its check of `closed` and its wait on `ready`.
+                let buffers = queue.buffers.lock();
+                queue_closed.store(true, std::sync::atomic::Ordering::Release);
+                drop(buffers);
+                queue.ready.notify_one();
+                let _ = dispatch_handle.join();
+            })
+            .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+
+        self.shutdown = Some(CallbackStreamShutdown {
+            running,
+            alive,
+            handle: Some(capture_handle),
+        });
+        Ok(())
+    }
+
+    /// Returns `true` if the capture request thread is actually still running — it hasn't exited
+    /// on its own (e.g. the device was unplugged) and [`stop()`](CallbackCaptureState::stop())
+    /// hasn't been called.
+    pub fn is_open(&self) -> bool {
+        self.shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.alive.load(std::sync::atomic::Ordering::Acquire))
+    }
+
+    /// Signals the capture request thread to stop and joins it.
+    /// # Errors
+    /// If the worker thread panicked, this will error.
+    pub fn stop(&mut self) -> Result<(), NokhwaError> {
+        if let Some(mut shutdown) = self.shutdown.take() {
+            shutdown.stop()?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for CallbackCaptureState {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+#[cfg(feature = "async")]
+#[cfg_attr(feature = "async", async_trait::async_trait)]
+/// Async counterpart of [`CallbackCapture`]: registers a callback that is invoked for every
+/// decoded [`Buffer`] produced by a background capture request task.
+pub trait AsyncCallbackCapture: AsyncCaptureTrait {
+    /// Spawns a background task that loops [`frame_async()`](AsyncCaptureTrait::frame_async())
+    /// and dispatches every decoded [`Buffer`] to `callback`.
+    /// # Errors
+    /// If the backend fails to open the stream, this will error.
+    async fn start_callback_stream_async<F>(&mut self, callback: F) -> Result<(), NokhwaError>
+    where
+        F: FnMut(Buffer) + Send + 'static;
+
+    /// Returns `true` if a callback task spawned by
+    /// [`start_callback_stream_async()`](AsyncCallbackCapture::start_callback_stream_async()) is
+    /// currently running.
+    fn is_callback_stream_open(&self) -> bool;
+
+    /// Stops the callback task started by
+    /// [`start_callback_stream_async()`](AsyncCallbackCapture::start_callback_stream_async()), if
+    /// any, and awaits its completion.
+    /// # Errors
+    /// If the task panicked, this will error.
+    async fn stop_callback_stream_async(&mut self) -> Result<(), NokhwaError>;
+}
+
+/// On-the-fly frame conversion layer that decouples the *device* output format from the
+/// *requested* stream format, so a [`CaptureTrait`] implementor can always hand back the
+/// [`FrameFormat`] a user asked for regardless of what the sensor produces. A persistent scratch
+/// buffer is reused across calls to [`convert()`](Converter::convert()) to avoid per-frame
+/// reallocation, and conversion is skipped entirely when the device and target formats already
+/// match.
+#[derive(Default)]
+pub struct Converter {
+    target: Option<FrameFormat>,
+    /// Scratch space for the RGB intermediate every conversion routes through. Cleared, never
+    /// dropped, so its allocation survives across calls to [`convert()`](Converter::convert()).
+    rgb_scratch: Vec<u8>,
+    /// Scratch space for the encoded target-format bytes. Cleared, never dropped, for the same
+    /// reason as `rgb_scratch`; the [`Buffer`] returned from [`convert()`](Converter::convert())
+    /// gets its own copy of the contents so this allocation stays ours to reuse next call.
+    scratch: Vec<u8>,
+}
+
+impl Converter {
+    /// Creates a [`Converter`] with no target format set; [`convert()`](Converter::convert())
+    /// will pass buffers through unchanged until [`set_target()`](Converter::set_target()) is
+    /// called.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the desired output [`FrameFormat`]. Pass `None` to disable conversion and pass
+    /// buffers through unchanged.
+    pub fn set_target(&mut self, format: Option<FrameFormat>) {
+        self.target = format;
+    }
+
+    /// Gets the currently configured target [`FrameFormat`], if any.
+    pub fn target(&self) -> Option<FrameFormat> {
+        self.target
+    }
+
+    /// Converts `buffer` to the configured target format. If no target is set, or `buffer` is
+    /// already in the target format, `buffer` is returned unchanged without touching the scratch
+    /// buffers.
+    /// # Errors
+    /// If there is no known conversion path between the buffer's format and the target format,
+    /// this will error.
+    pub fn convert(&mut self, buffer: Buffer) -> Result<Buffer, NokhwaError> {
+        let target = match self.target {
+            Some(target) => target,
+            None => return Ok(buffer),
+        };
+
+        if buffer.source_frame_format() == target {
+            return Ok(buffer);
+        }
+
+        decode_to_rgb_into(
+            buffer.resolution(),
+            buffer.source_frame_format(),
+            buffer.buffer(),
+            &mut self.rgb_scratch,
+        )?;
+
+        self.scratch.clear();
+        encode_from_rgb(buffer.resolution(), &self.rgb_scratch, target, &mut self.scratch)?;
+
+        // `self.scratch` keeps its allocation for the next call; the `Buffer` gets its own copy.
+        Ok(Buffer::new(buffer.resolution(), Cow::Owned(self.scratch.clone()), target))
+    }
+}
+
+/// Decodes `data` (in `source` format) to interleaved 8-bit RGB into `out`, the common
+/// intermediate format every conversion path in [`Converter`] routes through. `out` is cleared
+/// but its allocation is kept so repeated calls don't reallocate.
+fn decode_to_rgb_into(
+    resolution: Resolution,
+    source: FrameFormat,
+    data: &[u8],
+    out: &mut Vec<u8>,
+) -> Result<(), NokhwaError> {
+    out.clear();
+    match source {
+        FrameFormat::RGB => {
+            out.extend_from_slice(data);
+            Ok(())
+        }
+        FrameFormat::YUYV => {
+            yuyv_to_rgb(data, out);
+            Ok(())
+        }
+        FrameFormat::NV12 => nv12_to_rgb(data, resolution.width() as usize, resolution.height() as usize, out),
+        FrameFormat::MJPEG => {
+            let decoded = image::load_from_memory(data)
+                .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))?;
+            out.extend_from_slice(decoded.to_rgb8().as_raw());
+            Ok(())
+        }
+        other => Err(NokhwaError::ReadFrameError(format!(
+            "no conversion path from {other:?} to RGB"
+        ))),
+    }
+}
+
+/// Encodes interleaved 8-bit RGB `rgb` into `target`, appending the result to `out`.
+fn encode_from_rgb(
+    resolution: Resolution,
+    rgb: &[u8],
+    target: FrameFormat,
+    out: &mut Vec<u8>,
+) -> Result<(), NokhwaError> {
+    match target {
+        FrameFormat::RGB => {
+            out.extend_from_slice(rgb);
+            Ok(())
+        }
+        FrameFormat::YUYV => {
+            rgb_to_yuyv(rgb, out);
+            Ok(())
+        }
+        FrameFormat::NV12 => rgb_to_nv12(rgb, resolution.width() as usize, resolution.height() as usize, out),
+        FrameFormat::MJPEG => {
+            let mut cursor = std::io::Cursor::new(&mut *out);
+            image::codecs::jpeg::JpegEncoder::new(&mut cursor)
+                .encode(rgb, resolution.width(), resolution.height(), image::ColorType::Rgb8)
+                .map_err(|why| NokhwaError::ReadFrameError(why.to_string()))
+        }
+        other => Err(NokhwaError::ReadFrameError(format!(
+            "no conversion path from RGB to {other:?}"
+        ))),
+    }
+}
+
+/// Converts packed 4:2:2 YUYV (`Y0 U Y1 V` per pixel pair) to interleaved RGB (BT.601), appending
+/// the result to `out`.
+fn yuyv_to_rgb(data: &[u8], out: &mut Vec<u8>) {
+    out.reserve(data.len() * 2);
+    for quad in data.chunks_exact(4) {
+        let (y0, u, y1, v) = (quad[0] as f32, quad[1] as f32 - 128.0, quad[2] as f32, quad[3] as f32 - 128.0);
+        for y in [y0, y1] {
+            let y = y - 16.0;
+            out.push((1.164 * y + 1.596 * v).clamp(0.0, 255.0) as u8);
+            out.push((1.164 * y - 0.392 * u - 0.813 * v).clamp(0.0, 255.0) as u8);
+            out.push((1.164 * y + 2.017 * u).clamp(0.0, 255.0) as u8);
+        }
+    }
+}
+
+/// Converts interleaved RGB to packed 4:2:2 YUYV (BT.601), the inverse of [`yuyv_to_rgb()`].
+fn rgb_to_yuyv(data: &[u8], out: &mut Vec<u8>) {
+    for pair in data.chunks_exact(6) {
+        let (r0, g0, b0) = (pair[0] as f32, pair[1] as f32, pair[2] as f32);
+        let (r1, g1, b1) = (pair[3] as f32, pair[4] as f32, pair[5] as f32);
+        let y0 = 16.0 + 0.257 * r0 + 0.504 * g0 + 0.098 * b0;
+        let y1 = 16.0 + 0.257 * r1 + 0.504 * g1 + 0.098 * b1;
+        let u = 128.0 - 0.148 * r0 - 0.291 * g0 + 0.439 * b0;
+        let v = 128.0 + 0.439 * r0 - 0.368 * g0 - 0.071 * b0;
+        out.push(y0.clamp(0.0, 255.0) as u8);
+        out.push(u.clamp(0.0, 255.0) as u8);
+        out.push(y1.clamp(0.0, 255.0) as u8);
+        out.push(v.clamp(0.0, 255.0) as u8);
+    }
+}
+
+/// NV12 (and the RGB it round-trips through) subsamples chroma 2x2, so both dimensions must be
+/// even or the U/V-plane indexing in [`nv12_to_rgb()`]/[`rgb_to_nv12()`] would run out of bounds.
+fn check_nv12_dimensions(width: usize, height: usize) -> Result<(), NokhwaError> {
+    if width % 2 != 0 || height % 2 != 0 {
+        return Err(NokhwaError::ReadFrameError(format!(
+            "NV12 requires even width and height, got {width}x{height}"
+        )));
+    }
+    Ok(())
+}
+
+/// Converts NV12 (Y plane followed by an interleaved, half-resolution U/V plane) to interleaved
+/// RGB (BT.601), appending the result to `out`.
+/// # Errors
+/// If `width`/`height` are odd, or `data` is too short for `width`x`height` NV12, this will
+/// error rather than panic on an out-of-bounds index.
+fn nv12_to_rgb(data: &[u8], width: usize, height: usize, out: &mut Vec<u8>) -> Result<(), NokhwaError> {
+    check_nv12_dimensions(width, height)?;
+    let plane_len = width * height;
+    let expected_len = plane_len + plane_len / 2;
+    if data.len() < expected_len {
+        return Err(NokhwaError::ReadFrameError(format!(
+            "NV12 buffer too short: expected at least {expected_len} bytes for {width}x{height}, got {}",
+            data.len()
+        )));
+    }
+
+    let y_plane = &data[..plane_len];
+    let uv_plane = &data[plane_len..expected_len];
+    out.resize(plane_len * 3, 0);
+    for row in 0..height {
+        for col in 0..width {
+            let y = y_plane[row * width + col] as f32 - 16.0;
+            let uv_index = (row / 2) * width + (col / 2) * 2;
+            let u = uv_plane[uv_index] as f32 - 128.0;
+            let v = uv_plane[uv_index + 1] as f32 - 128.0;
+            let out_index = (row * width + col) * 3;
+            out[out_index] = (1.164 * y + 1.596 * v).clamp(0.0, 255.0) as u8;
+            out[out_index + 1] = (1.164 * y - 0.392 * u - 0.813 * v).clamp(0.0, 255.0) as u8;
+            out[out_index + 2] = (1.164 * y + 2.017 * u).clamp(0.0, 255.0) as u8;
+        }
+    }
+    Ok(())
+}
+
+/// Converts interleaved RGB to NV12, the inverse of [`nv12_to_rgb()`].
+/// # Errors
+/// If `width`/`height` are odd, or `data` is too short for `width`x`height` RGB, this will error
+/// rather than panic on an out-of-bounds index.
+fn rgb_to_nv12(data: &[u8], width: usize, height: usize, out: &mut Vec<u8>) -> Result<(), NokhwaError> {
+    check_nv12_dimensions(width, height)?;
+    let plane_len = width * height;
+    let expected_rgb_len = plane_len * 3;
+    if data.len() < expected_rgb_len {
+        return Err(NokhwaError::ReadFrameError(format!(
+            "RGB buffer too short: expected at least {expected_rgb_len} bytes for {width}x{height}, got {}",
+            data.len()
+        )));
+    }
+
+    out.resize(plane_len + plane_len / 2, 0);
+    let (y_plane, uv_plane) = out.split_at_mut(plane_len);
+    for row in 0..height {
+        for col in 0..width {
+            let idx = (row * width + col) * 3;
+            let (r, g, b) = (data[idx] as f32, data[idx + 1] as f32, data[idx + 2] as f32);
+            y_plane[row * width + col] = (16.0 + 0.257 * r + 0.504 * g + 0.098 * b).clamp(0.0, 255.0) as u8;
+        }
+    }
+    for row in (0..height).step_by(2) {
+        for col in (0..width).step_by(2) {
+            let idx = (row * width + col) * 3;
+            let (r, g, b) = (data[idx] as f32, data[idx + 1] as f32, data[idx + 2] as f32);
+            let u = 128.0 - 0.148 * r - 0.291 * g + 0.439 * b;
+            let v = 128.0 + 0.439 * r - 0.368 * g - 0.071 * b;
+            let uv_index = (row / 2) * width + (col / 2) * 2;
+            uv_plane[uv_index] = u.clamp(0.0, 255.0) as u8;
+            uv_plane[uv_index + 1] = v.clamp(0.0, 255.0) as u8;
+        }
+    }
+    Ok(())
+}
+
+/// Drop-in field for a [`CaptureTrait`] implementor that wants
+/// [`frame_with_meta()`](CaptureTrait::frame_with_meta()) to report real sequence numbers: an
+/// incrementing counter the backend bumps once per frame dequeued from the device.
+#[derive(Default)]
+pub struct FrameSequencer {
+    next_sequence: u64,
+}
+
+impl FrameSequencer {
+    /// Creates a [`FrameSequencer`] starting at sequence `0`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the current wall-clock time as a `Duration` since [`UNIX_EPOCH`](std::time::UNIX_EPOCH),
+    /// for passing to [`stamp()`](FrameSequencer::stamp()). Exposed as a separate call so a
+    /// backend can capture it immediately after dequeuing the raw buffer from the device, before
+    /// any decode or conversion work (e.g. a [`Converter`] pass) — otherwise that latency would
+    /// be baked into the timestamp.
+    pub fn now() -> std::time::Duration {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+    }
+
+    /// Stamps `buffer` with the next sequence number and the supplied `timestamp`, then advances
+    /// the counter. `timestamp` should reflect when the buffer was actually dequeued from the
+    /// device (see [`now()`](FrameSequencer::now())), not when `stamp()` happens to be called,
+    /// so downstream inter-frame latency calculations reflect true device timing rather than
+    /// decode latency.
+    pub fn stamp(&mut self, buffer: Buffer, timestamp: std::time::Duration) -> (Buffer, FrameMetadata) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        (buffer, FrameMetadata { sequence, timestamp })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_rgb(width: usize, height: usize, pixel: [u8; 3]) -> Vec<u8> {
+        pixel.repeat(width * height)
+    }
+
+    fn format(width: u32, height: u32, fourcc: FrameFormat, fps: u32) -> CameraFormat {
+        CameraFormat::new(Resolution::new(width, height), fourcc, fps)
+    }
+
+    fn assert_approx_eq(expected: &[u8], actual: &[u8], tolerance: i16) {
+        assert_eq!(expected.len(), actual.len());
+        for (a, b) in expected.iter().zip(actual.iter()) {
+            assert!(
+                (*a as i16 - *b as i16).abs() <= tolerance,
+                "expected ~{a}, got {b} (tolerance {tolerance})"
+            );
+        }
+    }
+
+    #[test]
+    fn yuyv_round_trip_recovers_original_rgb() {
+        let original = vec![10u8, 20, 30, 200, 210, 220];
+        let mut yuyv = Vec::new();
+        rgb_to_yuyv(&original, &mut yuyv);
+        assert_eq!(yuyv.len(), 4);
+
+        let mut round_tripped = Vec::new();
+        yuyv_to_rgb(&yuyv, &mut round_tripped);
+
+        assert_approx_eq(&original, &round_tripped, 4);
+    }
+
+    #[test]
+    fn nv12_round_trip_recovers_original_rgb() {
+        let (width, height) = (2, 2);
+        let original = solid_rgb(width, height, [80, 140, 200]);
+
+        let mut nv12 = Vec::new();
+        rgb_to_nv12(&original, width, height, &mut nv12).expect("even dimensions are valid");
+        assert_eq!(nv12.len(), width * height + (width * height) / 2);
+
+        let mut round_tripped = Vec::new();
+        nv12_to_rgb(&nv12, width, height, &mut round_tripped).expect("freshly encoded buffer is valid");
+
+        assert_approx_eq(&original, &round_tripped, 12);
+    }
+
+    #[test]
+    fn nv12_to_rgb_rejects_odd_dimensions() {
+        let data = vec![0u8; 100];
+        let mut out = Vec::new();
+        assert!(nv12_to_rgb(&data, 3, 4, &mut out).is_err());
+    }
+
+    #[test]
+    fn nv12_to_rgb_rejects_truncated_buffer() {
+        let data = vec![0u8; 4];
+        let mut out = Vec::new();
+        assert!(nv12_to_rgb(&data, 4, 4, &mut out).is_err());
+    }
+
+    #[test]
+    fn rgb_to_nv12_rejects_odd_dimensions() {
+        let data = vec![0u8; 3 * 4 * 4];
+        let mut out = Vec::new();
+        assert!(rgb_to_nv12(&data, 3, 4, &mut out).is_err());
+    }
+
+    #[test]
+    fn rgb_to_nv12_rejects_truncated_buffer() {
+        let data = vec![0u8; 4];
+        let mut out = Vec::new();
+        assert!(rgb_to_nv12(&data, 4, 4, &mut out).is_err());
+    }
+
+    #[test]
+    fn distance_from_is_zero_for_an_identical_format() {
+        let target = format(1920, 1080, FrameFormat::MJPEG, 30);
+        assert_eq!(target.distance_from(&target), 0);
+    }
+
+    #[test]
+    fn distance_from_grows_with_resolution_and_frame_rate_mismatch() {
+        let target = format(1920, 1080, FrameFormat::MJPEG, 30);
+        let close = format(1920, 1080, FrameFormat::MJPEG, 29);
+        let far = format(640, 480, FrameFormat::MJPEG, 15);
+
+        assert!(close.distance_from(&target) > 0);
+        assert!(far.distance_from(&target) > close.distance_from(&target));
+    }
+
+    #[test]
+    fn distance_from_penalizes_frame_format_mismatch_above_resolution_or_frame_rate() {
+        let target = format(1920, 1080, FrameFormat::MJPEG, 30);
+        let wrong_format_same_everything_else = format(1920, 1080, FrameFormat::YUYV, 30);
+        // Resolution/frame-rate mismatch alone, no matter how large, should never outweigh a
+        // format mismatch -- that's the whole point of the fixed penalty.
+        let way_off_resolution_and_frame_rate_same_format = format(64, 48, FrameFormat::MJPEG, 5);
+
+        assert!(
+            wrong_format_same_everything_else.distance_from(&target)
+                > way_off_resolution_and_frame_rate_same_format.distance_from(&target)
+        );
+    }
+
+    #[test]
+    fn distance_from_is_symmetric() {
+        let a = format(1920, 1080, FrameFormat::MJPEG, 30);
+        let b = format(1280, 720, FrameFormat::YUYV, 24);
+        assert_eq!(a.distance_from(&b), b.distance_from(&a));
+    }
+
+    /// Minimal [`CaptureTrait`] implementor used to exercise [`CallbackCaptureState`] and the
+    /// default stub methods without any real device. `frame()` yields `frames_remaining` buffers
+    /// and then starts erroring, simulating a device that drops out mid-stream.
+    struct FakeCapture {
+        info: CameraInfo,
+        format: CameraFormat,
+        open: bool,
+        frames_remaining: usize,
+    }
+
+    impl FakeCapture {
+        fn new(frames_remaining: usize) -> Self {
+            FakeCapture {
+                info: CameraInfo::new("fake", "fake capture for tests", "", CameraIndex::Index(0)),
+                format: format(4, 4, FrameFormat::MJPEG, 30),
+                open: false,
+                frames_remaining,
+            }
+        }
+    }
+
+    impl CaptureTrait for FakeCapture {
+        fn backend(&self) -> ApiBackend {
+            ApiBackend::Auto
+        }
+
+        fn camera_info(&self) -> &CameraInfo {
+            &self.info
+        }
+
+        fn refresh_camera_format(&mut self) -> Result<(), NokhwaError> {
+            Ok(())
+        }
+
+        fn camera_format(&self) -> Option<CameraFormat> {
+            Some(self.format)
+        }
+
+        fn set_camera_format(&mut self, new_fmt: CameraFormat) -> Result<(), NokhwaError> {
+            self.format = new_fmt;
+            Ok(())
+        }
+
+        fn compatible_list_by_resolution(
+            &mut self,
+            _fourcc: FrameFormat,
+        ) -> Result<HashMap<Resolution, Vec<FrameRate>>, NokhwaError> {
+            Ok(HashMap::new())
+        }
+
+        fn compatible_fourcc(&mut self) -> Result<Vec<FrameFormat>, NokhwaError> {
+            Ok(vec![])
+        }
+
+        fn resolution(&self) -> Option<Resolution> {
+            Some(self.format.resolution())
+        }
+
+        fn set_resolution(&mut self, new_res: Resolution) -> Result<(), NokhwaError> {
+            self.format = CameraFormat::new(new_res, self.format.format(), self.format.frame_rate());
+            Ok(())
+        }
+
+        fn frame_rate(&self) -> Option<u32> {
+            Some(self.format.frame_rate())
+        }
+
+        fn set_frame_rate(&mut self, new_fps: u32) -> Result<(), NokhwaError> {
+            self.format = CameraFormat::new(self.format.resolution(), self.format.format(), new_fps);
+            Ok(())
+        }
+
+        fn frame_format(&self) -> FrameFormat {
+            self.format.format()
+        }
+
+        fn set_frame_format(&mut self, fourcc: FrameFormat) -> Result<(), NokhwaError> {
+            self.format = CameraFormat::new(self.format.resolution(), fourcc, self.format.frame_rate());
+            Ok(())
+        }
+
+        fn camera_control(&self, _control: KnownCameraControl) -> Result<CameraControl, NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn camera_controls(&self) -> Result<Vec<CameraControl>, NokhwaError> {
+            Ok(vec![])
+        }
+
+        fn set_camera_control(
+            &mut self,
+            _id: KnownCameraControl,
+            _value: ControlValueSetter,
+        ) -> Result<(), NokhwaError> {
+            Err(NokhwaError::UnsupportedOperationError(self.backend()))
+        }
+
+        fn open_stream(&mut self) -> Result<(), NokhwaError> {
+            self.open = true;
+            Ok(())
+        }
+
+        fn is_stream_open(&self) -> bool {
+            self.open
+        }
+
+        fn frame(&mut self) -> Result<Buffer, NokhwaError> {
+            if self.frames_remaining == 0 {
+                return Err(NokhwaError::ReadFrameError("fake backend exhausted".to_string()));
+            }
+            self.frames_remaining -= 1;
+            Ok(Buffer::new(
+                self.format.resolution(),
+                Cow::Owned(vec![0u8; 3]),
+                self.format.format(),
+            ))
+        }
+
+        fn frame_raw(&mut self) -> Result<Cow<[u8]>, NokhwaError> {
+            Ok(Cow::Owned(vec![0u8; 3]))
+        }
+
+        fn stop_stream(&mut self) -> Result<(), NokhwaError> {
+            self.open = false;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn callback_capture_state_delivers_buffers_to_the_callback() {
+        let capture = std::sync::Arc::new(std::sync::Mutex::new(FakeCapture::new(usize::MAX)));
+        let received = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let callback_received = received.clone();
+
+        let mut state = CallbackCaptureState::default();
+        state
+            .start(capture, move |_buffer| {
+                callback_received.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            })
+            .expect("starting a callback stream against a fake backend should not fail");
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while received.load(std::sync::atomic::Ordering::SeqCst) == 0 && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(
+            received.load(std::sync::atomic::Ordering::SeqCst) > 0,
+            "callback should have received at least one buffer"
+        );
+
+        state.stop().expect("stopping a running callback stream should join cleanly");
+        assert!(!state.is_open());
+    }
+
+    #[test]
+    fn callback_capture_state_drop_joins_the_worker_thread() {
+        let capture = std::sync::Arc::new(std::sync::Mutex::new(FakeCapture::new(usize::MAX)));
+        let mut state = CallbackCaptureState::default();
+        state
+            .start(capture, |_buffer| {})
+            .expect("starting a callback stream against a fake backend should not fail");
+        assert!(state.is_open());
+        drop(state);
+    }
+
+    #[test]
+    fn callback_capture_state_is_open_becomes_false_once_the_backend_starts_erroring() {
+        let capture = std::sync::Arc::new(std::sync::Mutex::new(FakeCapture::new(1)));
+        let mut state = CallbackCaptureState::default();
+        state
+            .start(capture, |_buffer| {})
+            .expect("starting a callback stream against a fake backend should not fail");
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while state.is_open() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert!(
+            !state.is_open(),
+            "is_open() should go false once the fake backend's frame() starts erroring"
+        );
+    }
+
+    #[test]
+    fn open_streams_and_frame_for_default_to_unsupported_operation_error() {
+        let mut capture = FakeCapture::new(0);
+        assert!(matches!(
+            capture.open_streams(&[]),
+            Err(NokhwaError::UnsupportedOperationError(_))
+        ));
+        assert!(matches!(
+            capture.frame_for(StreamId(0)),
+            Err(NokhwaError::UnsupportedOperationError(_))
+        ));
+    }
+
+    #[test]
+    fn frame_sequencer_stamp_increments_sequence_and_passes_through_the_supplied_timestamp() {
+        let mut sequencer = FrameSequencer::new();
+        let buffer_a = Buffer::new(Resolution::new(4, 4), Cow::Owned(vec![0u8; 3]), FrameFormat::MJPEG);
+        let buffer_b = Buffer::new(Resolution::new(4, 4), Cow::Owned(vec![0u8; 3]), FrameFormat::MJPEG);
+
+        let timestamp_a = std::time::Duration::from_millis(1_000);
+        let timestamp_b = std::time::Duration::from_millis(2_000);
+
+        let (_, meta_a) = sequencer.stamp(buffer_a, timestamp_a);
+        let (_, meta_b) = sequencer.stamp(buffer_b, timestamp_b);
+
+        assert_eq!(meta_a.sequence, 0);
+        assert_eq!(meta_b.sequence, 1);
+        assert_eq!(meta_a.timestamp, timestamp_a);
+        assert_eq!(meta_b.timestamp, timestamp_b);
+    }
+}